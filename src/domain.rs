@@ -17,7 +17,8 @@ use crate::pairing::{
 
 use crate::pairing::ff::{
     Field, 
-    PrimeField
+    PrimeField,
+    PrimeFieldRepr
 };
 
 use super::{
@@ -49,6 +50,12 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
         self.coeffs
     }
 
+    /// Returns the inverse of the domain size, which is
+    /// the scaling factor of the inverse FFT.
+    pub fn size_inverse(&self) -> E::Fr {
+        self.minv
+    }
+
     pub fn from_coeffs(mut coeffs: Vec<G>) -> Result<EvaluationDomain<E, G>, SynthesisError>
     {
         use crate::pairing::ff::PrimeField;
@@ -94,7 +101,7 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
             omega: omega,
             omegainv: omega.inverse().unwrap(),
             geninv: E::Fr::multiplicative_generator().inverse().unwrap(),
-            minv: E::Fr::from_str(&format!("{}", m)).unwrap().inverse().unwrap()
+            minv: E::Fr::from_repr(<E::Fr as PrimeField>::Repr::from(m as u64)).unwrap().inverse().unwrap()
         })
     }
 
@@ -146,7 +153,7 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
             omega: omega,
             omegainv: omega.inverse().unwrap(),
             geninv: E::Fr::multiplicative_generator().inverse().unwrap(),
-            minv: E::Fr::from_str(&format!("{}", m)).unwrap().inverse().unwrap()
+            minv: E::Fr::from_repr(<E::Fr as PrimeField>::Repr::from(m as u64)).unwrap().inverse().unwrap()
         })
     }
 
@@ -202,6 +209,46 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
         self.distribute_powers(worker, geninv);
     }
 
+    /// Returns the coefficients of the first Lagrange basis polynomial,
+    /// which is one at omega^0 and zero on the rest of the domain. It is
+    /// (X^m - 1) / (m * (X - 1)), so every coefficient is 1/m.
+    pub fn l1_poly(&self) -> Vec<E::Fr> {
+        vec![self.minv; self.coeffs.len()]
+    }
+
+    /// Evaluates every Lagrange basis polynomial of this domain at `tau`.
+    /// This is the inverse FFT of the powers of `tau`.
+    pub fn evaluate_all_lagrange_at(&self, tau: E::Fr, worker: &Worker) -> Vec<E::Fr> {
+        let mut powers = vec![Scalar::<E>(E::Fr::zero()); self.coeffs.len()];
+
+        worker.scope(powers.len(), |scope, chunk| {
+            for (i, powers) in powers.chunks_mut(chunk).enumerate() {
+                scope.spawn(move |_| {
+                    let mut current = tau.pow([(i * chunk) as u64]);
+                    for p in powers.iter_mut() {
+                        p.0 = current;
+                        current.mul_assign(&tau);
+                    }
+                });
+            }
+        });
+
+        best_fft(&mut powers, worker, &self.omegainv, self.exp);
+
+        let minv = self.minv;
+        worker.scope(powers.len(), |scope, chunk| {
+            for powers in powers.chunks_mut(chunk) {
+                scope.spawn(move |_| {
+                    for p in powers.iter_mut() {
+                        p.0.mul_assign(&minv);
+                    }
+                });
+            }
+        });
+
+        powers.into_iter().map(|p| p.0).collect()
+    }
+
     /// This evaluates t(tau) for this domain, which is
     /// tau^m - 1 for these radix-2 domains.
     pub fn z(&self, tau: &E::Fr) -> E::Fr {
@@ -260,6 +307,87 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
     }
 }
 
+/// Checks that the field's two-adic root of unity has order exactly
+/// `2^S`, i.e. squaring it `S` times reaches one and no fewer squarings do.
+/// A failure means the field constants are misconfigured and every
+/// radix-2 domain built from them would be wrong.
+pub fn verify_root_chain<F: PrimeField>() -> Result<(), String> {
+    let mut root = F::root_of_unity();
+
+    for i in 0..F::S {
+        if root == F::one() {
+            return Err(format!("root of unity has order 2^{} instead of 2^{}", i, F::S));
+        }
+        root.square();
+    }
+
+    if root != F::one() {
+        return Err(format!("root of unity does not reach one after {} squarings", F::S));
+    }
+
+    Ok(())
+}
+
+/// Finds a primitive root of unity of the given `order`, or returns `None`
+/// if `order` does not divide the order of the multiplicative group.
+/// For powers of two this is the same root radix-2 domains use, other
+/// orders are derived from the multiplicative generator.
+pub fn find_root_of_unity<F: PrimeField>(order: u64) -> Option<F> {
+    if order == 0 {
+        return None;
+    }
+
+    if order.is_power_of_two() {
+        let log_order = order.trailing_zeros();
+        if log_order <= F::S {
+            let mut root = F::root_of_unity();
+            for _ in log_order..F::S {
+                root.square();
+            }
+
+            return Some(root);
+        }
+    }
+
+    // generator^((p - 1) / order) has order exactly `order`
+    let mut exp = F::char();
+    exp.sub_noborrow(&F::Repr::from(1));
+
+    let order = order as u128;
+    let mut remainder = 0u128;
+    for limb in exp.as_mut().iter_mut().rev() {
+        let tmp = (remainder << 64) | (*limb as u128);
+        *limb = (tmp / order) as u64;
+        remainder = tmp % order;
+    }
+
+    if remainder != 0 {
+        return None;
+    }
+
+    Some(F::multiplicative_generator().pow(exp.as_ref()))
+}
+
+/// Performs an FFT of `a` in place over the radix-2 domain of size `a.len()`.
+/// Returns `SynthesisError::PolynomialDegreeTooLarge` if the length is not
+/// a power of two or exceeds the two-adicity of the field.
+pub fn best_fft_checked<E: Engine, T: Group<E>>(a: &mut [T], worker: &Worker) -> Result<(), SynthesisError>
+{
+    if !a.len().is_power_of_two() {
+        return Err(SynthesisError::PolynomialDegreeTooLarge)
+    }
+
+    let log_n = a.len().trailing_zeros();
+    if log_n > E::Fr::S {
+        return Err(SynthesisError::PolynomialDegreeTooLarge)
+    }
+
+    let omega = find_root_of_unity::<E::Fr>(a.len() as u64).ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+    best_fft(a, worker, &omega, log_n);
+
+    Ok(())
+}
+
 pub(crate) fn best_fft<E: Engine, T: Group<E>>(a: &mut [T], worker: &Worker, omega: &E::Fr, log_n: u32)
 {
     let log_cpus = worker.log_num_cpus();
@@ -271,26 +399,97 @@ pub(crate) fn best_fft<E: Engine, T: Group<E>>(a: &mut [T], worker: &Worker, ome
     }
 }
 
-pub(crate) fn serial_fft<E: Engine, T: Group<E>>(a: &mut [T], omega: &E::Fr, log_n: u32)
+fn bitreverse(mut n: u32, l: u32) -> u32 {
+    let mut r = 0;
+    for _ in 0..l {
+        r = (r << 1) | (n & 1);
+        n >>= 1;
+    }
+    r
+}
+
+// Raw pointer to the slice being permuted, shared between threads that
+// touch disjoint sets of its elements
+struct PermutationPtr<T>(*mut T);
+
+unsafe impl<T: Send> Send for PermutationPtr<T> {}
+unsafe impl<T: Send> Sync for PermutationPtr<T> {}
+
+impl<T> PermutationPtr<T> {
+    fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+fn serial_bitreverse_permute<T>(a: &mut [T], log_n: u32)
 {
-    fn bitreverse(mut n: u32, l: u32) -> u32 {
-        let mut r = 0;
-        for _ in 0..l {
-            r = (r << 1) | (n & 1);
-            n >>= 1;
+    for k in 0..a.len() {
+        let rk = bitreverse(k as u32, log_n) as usize;
+        if k < rk {
+            a.swap(rk, k);
         }
-        r
+    }
+}
+
+fn bitreverse_permute<T: Send>(a: &mut [T], worker: &Worker)
+{
+    let n = a.len();
+
+    // lengths 0, 1 and 2 are fixed points of the permutation
+    if n <= 2 {
+        return;
+    }
+
+    assert!(n.is_power_of_two());
+    let log_n = n.trailing_zeros();
+
+    if log_n <= worker.log_num_cpus() {
+        serial_bitreverse_permute(a, log_n);
+
+        return;
     }
 
+    let ptr = PermutationPtr(a.as_mut_ptr());
+    let ptr = &ptr;
+
+    worker.scope(n, |scope, chunk| {
+        for start in (0..n).step_by(chunk) {
+            let end = std::cmp::min(start + chunk, n);
+            scope.spawn(move |_| {
+                for k in start..end {
+                    let rk = bitreverse(k as u32, log_n) as usize;
+                    if k < rk {
+                        // bit reversal is an involution, so the pairs (k, rk) are
+                        // disjoint and each is swapped only by the thread owning k
+                        unsafe { std::ptr::swap(ptr.get().add(k), ptr.get().add(rk)) };
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Reorders values stored in bit-reversed index order into natural order.
+/// Panics if the length of `a` is neither zero nor a power of two.
+pub fn to_natural_order<T: Send>(a: &mut [T], worker: &Worker)
+{
+    bitreverse_permute(a, worker);
+}
+
+/// Reorders values stored in natural index order into bit-reversed order.
+/// This is the inverse of `to_natural_order` (the permutation is an involution).
+/// Panics if the length of `a` is neither zero nor a power of two.
+pub fn to_bitreversed_order<T: Send>(a: &mut [T], worker: &Worker)
+{
+    bitreverse_permute(a, worker);
+}
+
+pub(crate) fn serial_fft<E: Engine, T: Group<E>>(a: &mut [T], omega: &E::Fr, log_n: u32)
+{
     let n = a.len() as u32;
     assert_eq!(n, 1 << log_n);
 
-    for k in 0..n {
-        let rk = bitreverse(k, log_n);
-        if k < rk {
-            a.swap(rk as usize, k as usize);
-        }
-    }
+    serial_bitreverse_permute(a, log_n);
 
     let mut m = 1;
     for _ in 0..log_n {
@@ -424,6 +623,184 @@ fn polynomial_arith() {
     test_mul::<Bls12, _>(rng);
 }
 
+#[test]
+fn domain_size_inverse() {
+    use crate::pairing::bls12_381::{Bls12, Fr};
+
+    for log_d in 0..12 {
+        let d = 1 << log_d;
+
+        let domain = EvaluationDomain::<Bls12, Scalar<Bls12>>::from_coeffs(vec![Scalar(Fr::zero()); d]).unwrap();
+        let mut tmp = Fr::from_str(&format!("{}", d)).unwrap();
+        tmp.mul_assign(&domain.size_inverse());
+
+        assert_eq!(tmp, Fr::one());
+    }
+}
+
+#[test]
+fn root_chain_terminates_at_one() {
+    use crate::pairing::bls12_381::Fr as BlsFr;
+    use crate::pairing::bn256::Fr as BnFr;
+    use crate::tests::dummy_engine::Fr as DummyFr;
+
+    assert!(verify_root_chain::<BlsFr>().is_ok());
+    assert!(verify_root_chain::<BnFr>().is_ok());
+    assert!(verify_root_chain::<DummyFr>().is_ok());
+}
+
+#[test]
+fn roots_of_unity_of_arbitrary_order() {
+    use crate::pairing::bls12_381::{Bls12, Fr};
+    use crate::tests::dummy_engine::Fr as DummyFr;
+
+    fn assert_primitive<F: PrimeField>(root: F, order: u64, prime_factors: &[u64]) {
+        assert_eq!(root.pow([order]), F::one());
+        for p in prime_factors {
+            assert!(root.pow([order / p]) != F::one());
+        }
+    }
+
+    for log_d in 0..10 {
+        let d = 1 << log_d;
+        let domain = EvaluationDomain::<Bls12, Scalar<Bls12>>::from_coeffs(vec![Scalar(Fr::zero()); d]).unwrap();
+        assert_eq!(find_root_of_unity::<Fr>(d as u64), Some(domain.omega));
+    }
+    assert!(find_root_of_unity::<Fr>(1 << (Fr::S + 1)).is_none());
+
+    assert_primitive(find_root_of_unity::<Fr>(3).unwrap(), 3, &[3]);
+    assert_primitive(find_root_of_unity::<Fr>(3 << 5).unwrap(), 3 << 5, &[2, 3]);
+    assert!(find_root_of_unity::<Fr>(5).is_none());
+    assert!(find_root_of_unity::<Fr>(0).is_none());
+
+    // 64513 - 1 = 2^10 * 3^2 * 7
+    assert_primitive(find_root_of_unity::<DummyFr>(9).unwrap(), 9, &[3]);
+    assert_primitive(find_root_of_unity::<DummyFr>(7).unwrap(), 7, &[7]);
+    assert_primitive(find_root_of_unity::<DummyFr>(64512).unwrap(), 64512, &[2, 3, 7]);
+    assert!(find_root_of_unity::<DummyFr>(5).is_none());
+}
+
+#[test]
+fn lagrange_basis() {
+    use crate::pairing::bls12_381::{Bls12, Fr};
+    use rand::{self, Rand};
+
+    let rng = &mut rand::thread_rng();
+    let worker = Worker::new();
+
+    for log_d in 0..8 {
+        let d = 1 << log_d;
+        let values = (0..d).map(|_| Scalar::<Bls12>(Fr::rand(rng))).collect::<Vec<_>>();
+        let domain = EvaluationDomain::from_coeffs(values.clone()).unwrap();
+
+        let l1 = domain.l1_poly().into_iter().map(Scalar::<Bls12>).collect::<Vec<_>>();
+        let mut l1 = EvaluationDomain::from_coeffs(l1).unwrap();
+        l1.fft(&worker);
+        for (i, v) in l1.as_ref().iter().enumerate() {
+            if i == 0 {
+                assert_eq!(v.0, Fr::one());
+            } else {
+                assert!(v.0.is_zero());
+            }
+        }
+
+        for k in 0..d {
+            let basis = domain.evaluate_all_lagrange_at(domain.omega.pow([k as u64]), &worker);
+            for (i, b) in basis.iter().enumerate() {
+                assert_eq!(*b == Fr::one(), i == k);
+                assert_eq!(b.is_zero(), i != k);
+            }
+        }
+
+        // sum of f(omega^i) * L_i(tau) is the interpolant evaluated at tau
+        let tau = Fr::rand(rng);
+        let mut expected = Fr::zero();
+        {
+            let mut coeffs = EvaluationDomain::from_coeffs(values.clone()).unwrap();
+            coeffs.ifft(&worker);
+            let mut power = Fr::one();
+            for c in coeffs.as_ref() {
+                let mut tmp = c.0;
+                tmp.mul_assign(&power);
+                expected.add_assign(&tmp);
+                power.mul_assign(&tau);
+            }
+        }
+        let mut interpolated = Fr::zero();
+        for (v, l) in values.iter().zip(domain.evaluate_all_lagrange_at(tau, &worker)) {
+            let mut tmp = v.0;
+            tmp.mul_assign(&l);
+            interpolated.add_assign(&tmp);
+        }
+        assert_eq!(interpolated, expected);
+    }
+}
+
+#[test]
+fn checked_fft() {
+    use crate::pairing::bls12_381::{Bls12, Fr};
+    use crate::tests::dummy_engine::{DummyEngine, Fr as DummyFr};
+    use rand::{self, Rand};
+
+    let rng = &mut rand::thread_rng();
+    let worker = Worker::new();
+
+    for log_d in 0..8 {
+        let v = (0..(1 << log_d)).map(|_| Scalar::<Bls12>(Fr::rand(rng))).collect::<Vec<_>>();
+
+        let mut domain = EvaluationDomain::from_coeffs(v.clone()).unwrap();
+        domain.fft(&worker);
+
+        let mut checked = v;
+        best_fft_checked(&mut checked, &worker).unwrap();
+        assert!(checked == domain.coeffs);
+    }
+
+    for len in &[0, 3, 12] {
+        let mut v = vec![Scalar::<Bls12>(Fr::one()); *len];
+        assert!(best_fft_checked(&mut v, &worker).is_err());
+    }
+
+    let mut v = vec![Scalar::<DummyEngine>(<DummyFr as Field>::one()); 1 << DummyFr::S];
+    assert!(best_fft_checked(&mut v, &worker).is_ok());
+
+    let mut v = vec![Scalar::<DummyEngine>(<DummyFr as Field>::one()); 1 << (DummyFr::S + 1)];
+    assert!(best_fft_checked(&mut v, &worker).is_err());
+}
+
+#[test]
+fn bitreversed_order_round_trip() {
+    use rand::{XorShiftRng, SeedableRng, Rand};
+    use crate::pairing::bls12_381::Fr;
+
+    let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut empty: Vec<usize> = vec![];
+    to_natural_order(&mut empty, &Worker::new());
+    to_bitreversed_order(&mut empty, &Worker::new());
+
+    let mut small: Vec<usize> = (0..8).collect();
+    to_bitreversed_order(&mut small, &Worker::new_with_cpus(1));
+    assert_eq!(small, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+
+    for log_n in 0..12 {
+        let original = (0..(1 << log_n)).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        for cpus in &[1, 2, 3, 4, 8] {
+            let worker = Worker::new_with_cpus(*cpus);
+
+            let mut v = original.clone();
+            to_bitreversed_order(&mut v, &worker);
+            for (i, x) in v.iter().enumerate() {
+                assert_eq!(*x, original[bitreverse(i as u32, log_n) as usize]);
+            }
+
+            to_natural_order(&mut v, &worker);
+            assert_eq!(v, original);
+        }
+    }
+}
+
 #[test]
 fn fft_composition() {
     use crate::pairing::bls12_381::Bls12;
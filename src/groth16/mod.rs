@@ -490,40 +490,40 @@ mod test_with_bls12_381 {
     use crate::pairing::ff::{Field};
     use crate::pairing::bls12_381::{Bls12, Fr};
 
-    #[test]
-    fn serialization() {
-        struct MySillyCircuit<E: Engine> {
-            a: Option<E::Fr>,
-            b: Option<E::Fr>
-        }
-
-        impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
-            fn synthesize<CS: ConstraintSystem<E>>(
-                self,
-                cs: &mut CS
-            ) -> Result<(), SynthesisError>
-            {
-                let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
-                let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
-                let c = cs.alloc_input(|| "c", || {
-                    let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
-                    let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
-
-                    a.mul_assign(&b);
-                    Ok(a)
-                })?;
-
-                cs.enforce(
-                    || "a*b=c",
-                    |lc| lc + a,
-                    |lc| lc + b,
-                    |lc| lc + c
-                );
+    struct MySillyCircuit<E: Engine> {
+        a: Option<E::Fr>,
+        b: Option<E::Fr>
+    }
 
-                Ok(())
-            }
+    impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
+        fn synthesize<CS: ConstraintSystem<E>>(
+            self,
+            cs: &mut CS
+        ) -> Result<(), SynthesisError>
+        {
+            let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+            let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+            let c = cs.alloc_input(|| "c", || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            })?;
+
+            cs.enforce(
+                || "a*b=c",
+                |lc| lc + a,
+                |lc| lc + b,
+                |lc| lc + c
+            );
+
+            Ok(())
         }
+    }
 
+    #[test]
+    fn serialization() {
         let rng = &mut thread_rng();
 
         let params = generate_random_parameters::<Bls12, _, _>(
@@ -573,4 +573,59 @@ mod test_with_bls12_381 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn deterministic_proof_bytes() {
+        use rand::{SeedableRng, XorShiftRng};
+        use crate::worker::Worker;
+
+        fn proof_bytes(worker: &Worker) -> Vec<u8> {
+            let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+            let params = generate_random_parameters::<Bls12, _, _>(
+                MySillyCircuit { a: None, b: None },
+                rng
+            ).unwrap();
+
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let r = Fr::rand(rng);
+            let s = Fr::rand(rng);
+
+            let proof = create_proof_with_worker(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b)
+                },
+                worker,
+                &params,
+                r,
+                s
+            ).unwrap();
+
+            let mut v = vec![];
+            proof.write(&mut v).unwrap();
+
+            v
+        }
+
+        // Proof bytes for a fixed seed must not depend on the build profile
+        // or on how the work is split between threads.
+        //
+        // The constant also depends on how `pairing_ce` samples field elements
+        // from the rng and serializes points (generated with 0.21.1), and the
+        // dependency is not pinned, so regenerate it whenever `pairing_ce` is
+        // bumped by printing `hex` below.
+        let expected = concat!(
+            "8e3fb901d5e94565cab8dd0b5e31863139c5d0a666513e827ae1125d0ebda4cbdabc6ea35302d05523d3d9c8378445adab3f7e11af6d1f7bb8b8ca5a343079bb",
+            "5122bef1fcec501ac9b7488e9716581803919e7691c3347effff2bfa48fcf3dc100a1839d62108322ff82ae889d69407b8f87b6de80d6841c8b29df2d2755683",
+            "f3edc58376a69848be0062413e923460b30cb81fedda9a8e7f2be582cb6f352f018bbed283d80027da20c9af042f0cfb3cfd5629a9163d6ff7ec195ed8d9529f"
+        );
+
+        for cpus in &[1, 2, 3, 8] {
+            let v = proof_bytes(&Worker::new_with_cpus(*cpus));
+            let hex: String = v.iter().map(|b| format!("{:02x}", b)).collect();
+            assert_eq!(hex, expected);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::log::Stopwatch;
 use rand::Rng;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::pairing::{
     Engine,
@@ -84,6 +85,44 @@ fn eval<E: Engine>(
     acc
 }
 
+// A * B - C is divisible by Z without a remainder if and only if it
+// vanishes on the domain, so it's checked on the evaluations directly
+// before the quotient is computed
+fn check_constraints_satisfied<E: Engine>(
+    worker: &Worker,
+    a: &[Scalar<E>],
+    b: &[Scalar<E>],
+    c: &[Scalar<E>]
+) -> Result<(), SynthesisError>
+{
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.len(), c.len());
+
+    let satisfied = AtomicBool::new(true);
+
+    worker.scope(a.len(), |scope, chunk| {
+        for ((a, b), c) in a.chunks(chunk).zip(b.chunks(chunk)).zip(c.chunks(chunk)) {
+            let satisfied = &satisfied;
+            scope.spawn(move |_| {
+                for ((a, b), c) in a.iter().zip(b.iter()).zip(c.iter()) {
+                    let mut tmp = a.0;
+                    tmp.mul_assign(&b.0);
+                    if tmp != c.0 {
+                        satisfied.store(false, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    if satisfied.load(Ordering::Relaxed) {
+        Ok(())
+    } else {
+        Err(SynthesisError::Unsatisfiable)
+    }
+}
+
 pub(crate) fn field_elements_into_representations<E: Engine>(
     worker: &Worker,
     scalars: Vec<E::Fr>
@@ -199,18 +238,39 @@ impl<E:Engine> PreparedProver<E> {
 
     pub fn create_proof<P: ParameterSource<E>>(
         self,
+        params: P,
+        r: E::Fr,
+        s: E::Fr
+    ) -> Result<Proof<E>, SynthesisError>
+    {
+        let worker = Worker::new();
+
+        self.create_proof_with_worker(&worker, params, r, s)
+    }
+
+    /// Creates a proof using an existing `worker`. Proofs created concurrently
+    /// with clones of one worker share its thread pool for the multiexps, but
+    /// the FFTs and the constraint check run through `Worker::scope`, which
+    /// spawns up to the worker's number of CPUs in fresh threads on each call,
+    /// so those stages of concurrent proofs still compete for cores.
+    pub fn create_proof_with_worker<P: ParameterSource<E>>(
+        self,
+        worker: &Worker,
         mut params: P,
         r: E::Fr,
         s: E::Fr
     ) -> Result<Proof<E>, SynthesisError>
     {
         let prover = self.assignment;
-        let worker = Worker::new();
 
         let vk = params.get_vk(prover.input_assignment.len())?;
 
         let _stopwatch = Stopwatch::new();
 
+        // a quotient computed from an unsatisfied assignment has a non-zero
+        // remainder that would be silently dropped, so refuse to prove
+        check_constraints_satisfied(worker, &prover.a, &prover.b, &prover.c)?;
+
         let h = {
             let mut a = EvaluationDomain::from_coeffs(prover.a)?;
             let mut b = EvaluationDomain::from_coeffs(prover.b)?;
@@ -219,33 +279,33 @@ impl<E:Engine> PreparedProver<E> {
 
             // here a coset is a domain where denominator (z) does not vanish
             // inverse FFT is an interpolation
-            a.ifft(&worker);
+            a.ifft(worker);
             // evaluate in coset
-            a.coset_fft(&worker);
+            a.coset_fft(worker);
             // same is for B and C
-            b.ifft(&worker);
-            b.coset_fft(&worker);
-            c.ifft(&worker);
-            c.coset_fft(&worker);
+            b.ifft(worker);
+            b.coset_fft(worker);
+            c.ifft(worker);
+            c.coset_fft(worker);
 
             // do A*B-C in coset
-            a.mul_assign(&worker, &b);
+            a.mul_assign(worker, &b);
             drop(b);
-            a.sub_assign(&worker, &c);
+            a.sub_assign(worker, &c);
             drop(c);
             // z does not vanish in coset, so we divide by non-zero
-            a.divide_by_z_on_coset(&worker);
+            a.divide_by_z_on_coset(worker);
             // interpolate back in coset
-            a.icoset_fft(&worker);
+            a.icoset_fft(worker);
             let mut a = a.into_coeffs();
             let a_len = a.len() - 1;
             a.truncate(a_len);
             // TODO: parallelize if it's even helpful
             // TODO: in large settings it may worth to parallelize
-            let a = Arc::new(scalars_into_representations::<E>(&worker, a)?);
+            let a = Arc::new(scalars_into_representations::<E>(worker, a)?);
             // let a = Arc::new(a.into_iter().map(|s| s.0.into_repr()).collect::<Vec<_>>());
 
-            multiexp(&worker, params.get_h(a.len())?, FullDensity, a)
+            multiexp(worker, params.get_h(a.len())?, FullDensity, a)
         };
 
         elog_verbose!("{} seconds for prover for H evaluation (mostly FFT)", _stopwatch.elapsed());
@@ -262,8 +322,8 @@ impl<E:Engine> PreparedProver<E> {
                 2*(input_len + aux_len) + aux_len, input_len + aux_len);
         }
 
-        let input_assignment = Arc::new(field_elements_into_representations::<E>(&worker, prover.input_assignment)?);
-        let aux_assignment = Arc::new(field_elements_into_representations::<E>(&worker, prover.aux_assignment)?);
+        let input_assignment = Arc::new(field_elements_into_representations::<E>(worker, prover.input_assignment)?);
+        let aux_assignment = Arc::new(field_elements_into_representations::<E>(worker, prover.aux_assignment)?);
 
         // TODO: parallelize if it's even helpful
         // TODO: in large settings it may worth to parallelize
@@ -274,14 +334,14 @@ impl<E:Engine> PreparedProver<E> {
         // let aux_len = aux_assignment.len();
 
         // Run a dedicated process for dense vector
-        let l = multiexp(&worker, params.get_l(aux_assignment.len())?, FullDensity, aux_assignment.clone());
+        let l = multiexp(worker, params.get_l(aux_assignment.len())?, FullDensity, aux_assignment.clone());
 
         let a_aux_density_total = prover.a_aux_density.get_total_density();
 
         let (a_inputs_source, a_aux_source) = params.get_a(input_assignment.len(), a_aux_density_total)?;
 
-        let a_inputs = multiexp(&worker, a_inputs_source, FullDensity, input_assignment.clone());
-        let a_aux = multiexp(&worker, a_aux_source, Arc::new(prover.a_aux_density), aux_assignment.clone());
+        let a_inputs = multiexp(worker, a_inputs_source, FullDensity, input_assignment.clone());
+        let a_aux = multiexp(worker, a_aux_source, Arc::new(prover.a_aux_density), aux_assignment.clone());
 
         let b_input_density = Arc::new(prover.b_input_density);
         let b_input_density_total = b_input_density.get_total_density();
@@ -290,13 +350,13 @@ impl<E:Engine> PreparedProver<E> {
 
         let (b_g1_inputs_source, b_g1_aux_source) = params.get_b_g1(b_input_density_total, b_aux_density_total)?;
 
-        let b_g1_inputs = multiexp(&worker, b_g1_inputs_source, b_input_density.clone(), input_assignment.clone());
-        let b_g1_aux = multiexp(&worker, b_g1_aux_source, b_aux_density.clone(), aux_assignment.clone());
+        let b_g1_inputs = multiexp(worker, b_g1_inputs_source, b_input_density.clone(), input_assignment.clone());
+        let b_g1_aux = multiexp(worker, b_g1_aux_source, b_aux_density.clone(), aux_assignment.clone());
 
         let (b_g2_inputs_source, b_g2_aux_source) = params.get_b_g2(b_input_density_total, b_aux_density_total)?;
         
-        let b_g2_inputs = multiexp(&worker, b_g2_inputs_source, b_input_density, input_assignment);
-        let b_g2_aux = multiexp(&worker, b_g2_aux_source, b_aux_density, aux_assignment);
+        let b_g2_inputs = multiexp(worker, b_g2_inputs_source, b_input_density, input_assignment);
+        let b_g2_aux = multiexp(worker, b_g2_aux_source, b_aux_density, aux_assignment);
 
         if vk.delta_g1.is_zero() || vk.delta_g2.is_zero() {
             // If this element is zero, someone is trying to perform a
@@ -462,3 +522,17 @@ pub fn create_proof<E, C, P: ParameterSource<E>>(
 
     prover.create_proof(params, r, s)
 }
+
+pub fn create_proof_with_worker<E, C, P: ParameterSource<E>>(
+    circuit: C,
+    worker: &Worker,
+    params: P,
+    r: E::Fr,
+    s: E::Fr
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>
+{
+    let prover = prepare_prover(circuit)?;
+
+    prover.create_proof_with_worker(worker, params, r, s)
+}
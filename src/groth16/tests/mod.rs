@@ -22,6 +22,7 @@ use super::{
     generate_parameters,
     prepare_verifying_key,
     create_proof,
+    create_proof_with_worker,
     verify_proof
 };
 
@@ -328,3 +329,149 @@ fn test_xordemo() {
         &[Fr::one()]
     ).unwrap());
 }
+
+struct UnsatisfiedDemo<E: Engine> {
+    a: Option<E::Fr>,
+    b: Option<E::Fr>,
+    c: Option<E::Fr>
+}
+
+impl<E: Engine> Circuit<E> for UnsatisfiedDemo<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(|| "c", || self.c.ok_or(SynthesisError::AssignmentMissing))?;
+
+        cs.enforce(
+            || "a*b=c",
+            |lc| lc + a,
+            |lc| lc + b,
+            |lc| lc + c
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_unsatisfied_assignment_is_rejected() {
+    let params = {
+        let c = UnsatisfiedDemo::<DummyEngine> {
+            a: None,
+            b: None,
+            c: None
+        };
+
+        generate_parameters(
+            c,
+            Fr::one(),
+            Fr::one(),
+            Fr::from_str("48577").unwrap(),
+            Fr::from_str("22580").unwrap(),
+            Fr::from_str("53332").unwrap(),
+            Fr::from_str("5481").unwrap(),
+            Fr::from_str("3673").unwrap()
+        ).unwrap()
+    };
+
+    let r = Fr::from_str("27134").unwrap();
+    let s = Fr::from_str("17146").unwrap();
+
+    let c = UnsatisfiedDemo::<DummyEngine> {
+        a: Some(Fr::from_str("2").unwrap()),
+        b: Some(Fr::from_str("3").unwrap()),
+        c: Some(Fr::from_str("6").unwrap())
+    };
+    assert!(create_proof(c, &params, r, s).is_ok());
+
+    let c = UnsatisfiedDemo::<DummyEngine> {
+        a: Some(Fr::from_str("2").unwrap()),
+        b: Some(Fr::from_str("3").unwrap()),
+        c: Some(Fr::from_str("7").unwrap())
+    };
+    match create_proof(c, &params, r, s) {
+        Err(SynthesisError::Unsatisfiable) => {},
+        _ => panic!("proof must not be created for an unsatisfied assignment")
+    }
+}
+
+#[test]
+fn test_concurrent_proofs_share_worker() {
+    use crate::worker::Worker;
+    use std::sync::Arc;
+
+    let params = {
+        let c = XORDemo::<DummyEngine> {
+            a: None,
+            b: None,
+            _marker: PhantomData
+        };
+
+        Arc::new(generate_parameters(
+            c,
+            Fr::one(),
+            Fr::one(),
+            Fr::from_str("48577").unwrap(),
+            Fr::from_str("22580").unwrap(),
+            Fr::from_str("53332").unwrap(),
+            Fr::from_str("5481").unwrap(),
+            Fr::from_str("3673").unwrap()
+        ).unwrap())
+    };
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::channel;
+
+    // a single pool thread, so clones only make progress if they share it
+    let worker = Worker::new_with_cpus(1);
+
+    // a task queued on a clone must wait for the one blocking the original
+    let released = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = channel::<()>();
+    let blocker = {
+        let released = released.clone();
+        worker.compute(move || {
+            receiver.recv().map_err(|_| ())?;
+            released.store(true, Ordering::SeqCst);
+
+            Ok::<_, ()>(())
+        })
+    };
+    let probe = {
+        let released = released.clone();
+        worker.clone().compute(move || Ok::<_, ()>(released.load(Ordering::SeqCst)))
+    };
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    sender.send(()).unwrap();
+    blocker.wait().unwrap();
+    assert!(probe.wait().unwrap(), "cloned worker must share the thread pool");
+
+    let handles = [(true, false, "27134"), (false, true, "7117")].iter().map(|&(a, b, r)| {
+        let params = params.clone();
+        let worker = worker.clone();
+
+        std::thread::spawn(move || {
+            let c = XORDemo {
+                a: Some(a),
+                b: Some(b),
+                _marker: PhantomData
+            };
+
+            let r = Fr::from_str(r).unwrap();
+            let s = Fr::from_str("17146").unwrap();
+
+            create_proof_with_worker(c, &worker, &*params, r, s).unwrap()
+        })
+    }).collect::<Vec<_>>();
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    for handle in handles {
+        let proof = handle.join().unwrap();
+        assert!(verify_proof(&pvk, &proof, &[Fr::one()]).unwrap());
+    }
+}
@@ -11,6 +11,7 @@ extern crate crossbeam;
 use std::future::{Future};
 use std::task::{Context, Poll};
 use std::pin::{Pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use self::crossbeam::thread::{Scope};
 
@@ -19,6 +20,12 @@ use self::futures::channel::oneshot::{channel, Sender, Receiver};
 use self::futures::executor::{block_on};
 use self::futures::executor::{ThreadPool};
 
+// Number of threads `Worker::new()` should use if set to a non-zero value
+static DEFAULT_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// Environment variable that bounds the number of threads used by `Worker::new()`
+const NUM_THREADS_ENV: &str = "BELLMAN_NUM_THREADS";
+
 #[derive(Clone)]
 pub struct Worker {
     cpus: usize,
@@ -27,10 +34,9 @@ pub struct Worker {
 
 
 impl Worker {
-    // We don't expose this outside the library so that
-    // all `Worker` instances have the same number of
-    // CPUs configured.
-    
+    // We don't expose this outside the library, the number of
+    // threads of a public `Worker` is controlled by `Worker::new()`
+    // through `set_default_threads` and `BELLMAN_NUM_THREADS`.
     pub(crate) fn new_with_cpus(cpus: usize) -> Worker {
         Worker {
             cpus: cpus,
@@ -38,8 +44,24 @@ impl Worker {
         }
     }
 
+    /// Creates a worker with the default number of threads. This is the value
+    /// set by `Worker::set_default_threads` if any, otherwise the value of the
+    /// `BELLMAN_NUM_THREADS` environment variable if it is set to a positive integer,
+    /// otherwise the number of CPUs.
     pub fn new() -> Worker {
-        Self::new_with_cpus(num_cpus::get())
+        Self::new_with_cpus(Self::default_threads())
+    }
+
+    /// Sets a process-global number of threads used by `Worker::new()`.
+    /// Passing zero removes the override.
+    pub fn set_default_threads(threads: usize) {
+        DEFAULT_THREADS.store(threads, Ordering::SeqCst);
+    }
+
+    fn default_threads() -> usize {
+        let env = std::env::var(NUM_THREADS_ENV).ok();
+
+        resolve_threads(DEFAULT_THREADS.load(Ordering::SeqCst), env.as_deref(), num_cpus::get())
     }
 
     pub fn log_num_cpus(&self) -> u32 {
@@ -71,6 +93,10 @@ impl Worker {
         worker_future
     }
 
+    /// Runs `f` in a scope with a chunk size derived from the number
+    /// of `elements` and CPUs. Chunk boundaries depend on the number of
+    /// threads, which is fine for elementwise work like in-place power
+    /// fills, as each element's value doesn't depend on its chunk.
     pub fn scope<'a, F, R>(
         &self,
         elements: usize,
@@ -88,6 +114,52 @@ impl Worker {
             f(scope, chunk_size)
         }).expect("must run")
     }
+
+    /// Splits `elements` into chunks of a fixed `chunk_size` and maps each
+    /// of them with `f` on at most the worker's number of CPUs threads, the
+    /// thread `i` taking chunks `i, i + cpus, ...`. Results are returned in
+    /// chunk order, so the split and the order of per-chunk results don't
+    /// depend on the number of threads. Use it when per-chunk results are
+    /// combined and must not depend on the machine.
+    pub fn map_chunks<T, R, F>(
+        &self,
+        elements: &[T],
+        chunk_size: usize,
+        f: F
+    ) -> Vec<R>
+        where T: Sync,
+              R: Send,
+              F: Fn(&[T]) -> R + Sync
+    {
+        assert!(chunk_size > 0);
+
+        let chunks: Vec<&[T]> = elements.chunks(chunk_size).collect();
+        let threads = std::cmp::min(self.cpus, chunks.len());
+
+        if threads <= 1 {
+            return chunks.into_iter().map(f).collect();
+        }
+
+        let f = &f;
+        let chunks = &chunks;
+
+        let mapped: Vec<Vec<(usize, R)>> = crossbeam::scope(|scope| {
+            let handles: Vec<_> = (0..threads).map(|t| {
+                scope.spawn(move |_| {
+                    (t..chunks.len()).step_by(threads).map(|i| (i, f(chunks[i]))).collect::<Vec<_>>()
+                })
+            }).collect();
+
+            handles.into_iter().map(|h| h.join().expect("must run")).collect()
+        }).expect("must run");
+
+        let mut results: Vec<Option<R>> = (0..chunks.len()).map(|_| None).collect();
+        for (i, r) in mapped.into_iter().flatten() {
+            results[i] = Some(r);
+        }
+
+        results.into_iter().map(|r| r.expect("every chunk is mapped")).collect()
+    }
 }
 
 pub struct WorkerFuture<T, E> {
@@ -121,6 +193,18 @@ impl<T: Send + 'static, E: Send + 'static> WorkerFuture<T, E> {
     }
 }
 
+// Picks the number of threads from the `set_default_threads` override
+// (zero if unset), the value of `BELLMAN_NUM_THREADS` and the number of CPUs
+fn resolve_threads(override_threads: usize, env: Option<&str>, cpus: usize) -> usize {
+    if override_threads > 0 {
+        return override_threads;
+    }
+
+    env.and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(cpus)
+}
+
 fn log2_floor(num: usize) -> u32 {
     assert!(num > 0);
 
@@ -145,6 +229,57 @@ fn test_log2_floor() {
     assert_eq!(log2_floor(8), 3);
 }
 
+#[test]
+fn test_resolve_threads() {
+    assert_eq!(resolve_threads(0, None, 8), 8);
+    assert_eq!(resolve_threads(2, None, 8), 2);
+
+    assert_eq!(resolve_threads(0, Some("3"), 8), 3);
+    assert_eq!(resolve_threads(0, Some(" 3\n"), 8), 3);
+    assert_eq!(resolve_threads(5, Some("3"), 8), 5);
+
+    // unusable values fall back to the number of CPUs
+    assert_eq!(resolve_threads(0, Some("0"), 8), 8);
+    assert_eq!(resolve_threads(0, Some(""), 8), 8);
+    assert_eq!(resolve_threads(0, Some("-1"), 8), 8);
+    assert_eq!(resolve_threads(0, Some("many"), 8), 8);
+}
+
+#[test]
+fn test_map_chunks() {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    let values: Vec<u64> = (0..1000).map(|i| i * i).collect();
+    let expected: Vec<u64> = values.chunks(8).map(|c| c.iter().sum()).collect();
+
+    for cpus in 1..9 {
+        let worker = Worker::new_with_cpus(cpus);
+
+        let threads = Mutex::new(HashSet::new());
+        let sums = worker.map_chunks(&values, 8, |chunk| {
+            threads.lock().unwrap().insert(std::thread::current().id());
+            chunk.iter().sum::<u64>()
+        });
+
+        // 125 chunks must still run on no more than `cpus` threads
+        assert!(threads.lock().unwrap().len() <= cpus);
+        assert_eq!(sums, expected);
+
+        // the same reduction through `scope` with its own chunking
+        let mut partial = vec![0u64; values.len()];
+        worker.scope(values.len(), |scope, chunk| {
+            for (v, s) in values.chunks(chunk).zip(partial.iter_mut()) {
+                scope.spawn(move |_| {
+                    *s = v.iter().sum();
+                });
+            }
+        });
+
+        assert_eq!(sums.iter().sum::<u64>(), partial.iter().sum::<u64>());
+    }
+}
+
 #[test]
 fn test_trivial_spawning() {
     use self::futures::executor::block_on;
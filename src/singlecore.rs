@@ -30,6 +30,9 @@ impl Worker {
         Self::new_with_cpus(1)
     }
 
+    /// Has no effect, single core worker always uses one thread.
+    pub fn set_default_threads(_threads: usize) {}
+
     pub fn log_num_cpus(&self) -> u32 {
         0u32
     }
@@ -68,6 +71,19 @@ impl Worker {
 
         f(&scope, chunk_size)
     }
+
+    pub fn map_chunks<T, R, F>(
+        &self,
+        elements: &[T],
+        chunk_size: usize,
+        f: F
+    ) -> Vec<R>
+        where F: Fn(&[T]) -> R
+    {
+        assert!(chunk_size > 0);
+
+        elements.chunks(chunk_size).map(f).collect()
+    }
 }
 #[derive(Clone)]
 pub struct Scope<'a> {
@@ -1,6 +1,7 @@
 use crate::SynthesisError;
 use crate::pairing::ff::{Field, PrimeField, PrimeFieldRepr, ScalarEngine};
 use crate::pairing::{CurveAffine, CurveProjective, Engine};
+use crate::multicore::Worker;
 use super::srs::SRS;
 
 pub trait ChainExt: Iterator {
@@ -462,6 +463,63 @@ where
     q
 }
 
+/// Divides polynomial `a` in `x` by `x - b` returning
+/// the quotient and the remainder, which is `a(b)`.
+pub fn divide_by_linear<F: Field>(a: &[F], b: F) -> (Vec<F>, F) {
+    if a.is_empty() {
+        return (vec![], F::zero());
+    }
+
+    let mut q = vec![F::zero(); a.len() - 1];
+
+    // synthetic division, the running value is a Horner's scheme evaluation at `b`
+    let mut tmp = F::zero();
+    for (i, coeff) in a.iter().enumerate().rev() {
+        tmp.mul_assign(&b);
+        tmp.add_assign(coeff);
+        if i > 0 {
+            q[i - 1] = tmp;
+        }
+    }
+
+    (q, tmp)
+}
+
+/// Divides polynomial `num` by polynomial `den`, both in coefficient form,
+/// returning the quotient and the remainder of degree less than `den`.
+pub fn poly_div<F: Field>(num: &[F], den: &[F]) -> Result<(Vec<F>, Vec<F>), SynthesisError> {
+    let den_len = match den.iter().rposition(|c| !c.is_zero()) {
+        Some(i) => i + 1,
+        None => return Err(SynthesisError::DivisionByZero),
+    };
+    let den = &den[..den_len];
+
+    if num.len() < den_len {
+        return Ok((vec![], num.to_vec()));
+    }
+
+    let lead_inv = den[den_len - 1].inverse().ok_or(SynthesisError::DivisionByZero)?;
+
+    let mut r = num.to_vec();
+    let mut q = vec![F::zero(); num.len() - den_len + 1];
+
+    for i in (0..q.len()).rev() {
+        let mut lead_coeff = r[i + den_len - 1];
+        lead_coeff.mul_assign(&lead_inv);
+        q[i] = lead_coeff;
+
+        for (r, d) in r[i..].iter_mut().zip(den.iter()) {
+            let mut tmp = *d;
+            tmp.mul_assign(&lead_coeff);
+            r.sub_assign(&tmp);
+        }
+    }
+
+    r.truncate(den_len - 1);
+
+    Ok((q, r))
+}
+
 /// Divides polynomial `a` in `x` by `x - b` with
 /// no remainder using fft.
 pub fn parallel_kate_divison<'a, E: Engine, I: IntoIterator<Item = &'a E::Fr>>(a: I, b: E::Fr) -> Vec<E::Fr>
@@ -628,25 +686,29 @@ fn laurent_division() {
 }
 
 pub fn multiply_polynomials<E: Engine>(a: Vec<E::Fr>, b: Vec<E::Fr>) -> Vec<E::Fr> {
+    let worker = Worker::new();
+
+    multiply_polynomials_with_worker::<E>(a, b, &worker)
+}
+
+pub fn multiply_polynomials_with_worker<E: Engine>(a: Vec<E::Fr>, b: Vec<E::Fr>, worker: &Worker) -> Vec<E::Fr> {
     let result_len = a.len() + b.len() - 1;
 
-    use crate::multicore::Worker;
     use crate::domain::{EvaluationDomain, Scalar};
 
-    let worker = Worker::new();
     let scalars_a: Vec<Scalar<E>> = a.into_iter().map(|e| Scalar::<E>(e)).collect();
     let mut domain_a = EvaluationDomain::from_coeffs_into_sized(scalars_a, result_len).unwrap();
 
     let scalars_b: Vec<Scalar<E>> = b.into_iter().map(|e| Scalar::<E>(e)).collect();
     let mut domain_b = EvaluationDomain::from_coeffs_into_sized(scalars_b, result_len).unwrap();
 
-    domain_a.fft(&worker);
-    domain_b.fft(&worker);
+    domain_a.fft(worker);
+    domain_b.fft(worker);
 
-    domain_a.mul_assign(&worker, &domain_b);
+    domain_a.mul_assign(worker, &domain_b);
     drop(domain_b);
 
-    domain_a.ifft(&worker);
+    domain_a.ifft(worker);
 
     let mut mul_result: Vec<E::Fr> = domain_a.into_coeffs().iter().map(|e| e.0).collect();
 
@@ -833,17 +895,33 @@ pub fn sub_polynomials<F: Field>(a: &mut [F], b: &[F]) {
 
 // multiply coefficients of the polynomial by the scalar
 pub fn mul_polynomial_by_scalar<F: Field>(a: &mut [F], b: F) {
-        use crate::multicore::Worker;
-        use crate::domain::{EvaluationDomain, Scalar};
-
         let worker = Worker::new();
 
+        scale_in_place(a, b, &worker);
+}
+
+// multiply coefficients of the polynomial by the scalar
+pub fn scale_in_place<F: Field>(a: &mut [F], s: F, worker: &Worker) {
         worker.scope(a.len(), |scope, chunk| {
             for a in a.chunks_mut(chunk)
             {
                 scope.spawn(move |_| {
                     for a in a.iter_mut() {
-                        a.mul_assign(&b);
+                        a.mul_assign(&s);
+                    }
+                });
+            }
+        });
+}
+
+// negate coefficients of the polynomial
+pub fn negate_in_place<F: Field>(a: &mut [F], worker: &Worker) {
+        worker.scope(a.len(), |scope, chunk| {
+            for a in a.chunks_mut(chunk)
+            {
+                scope.spawn(move |_| {
+                    for a in a.iter_mut() {
+                        a.negate();
                     }
                 });
             }
@@ -875,6 +953,99 @@ pub fn mul_add_polynomials<F: Field>(a: &mut [F], b: &[F], c: F) {
         });
 }
 
+// formal derivative of the polynomial in coefficient form, the derivative
+// of a constant is the zero polynomial with no coefficients, like the zero
+// quotient returned by `poly_div`
+pub fn derivative<F: Field>(a: &[F]) -> Vec<F> {
+    if a.len() <= 1 {
+        return vec![];
+    }
+
+    let mut result = Vec::with_capacity(a.len() - 1);
+    let mut i = F::zero();
+    for coeff in a[1..].iter() {
+        i.add_assign(&F::one());
+        let mut tmp = *coeff;
+        tmp.mul_assign(&i);
+        result.push(tmp);
+    }
+
+    result
+}
+
+// evaluations of the derivative of a polynomial on the domain, given the
+// polynomial's evaluations on the same domain of size `evals.len()`, which
+// must be a power of two supported by the field
+pub fn derivative_on_domain<E: Engine>(evals: Vec<E::Fr>, worker: &Worker) -> Result<Vec<E::Fr>, SynthesisError> {
+    use crate::domain::{EvaluationDomain, Scalar};
+
+    if !evals.len().is_power_of_two() {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+    let size = evals.len();
+
+    let scalars: Vec<Scalar<E>> = evals.into_iter().map(|e| Scalar::<E>(e)).collect();
+    let mut domain = EvaluationDomain::from_coeffs(scalars)?;
+    domain.ifft(worker);
+
+    let coeffs: Vec<E::Fr> = domain.into_coeffs().into_iter().map(|e| e.0).collect();
+    let mut coeffs: Vec<Scalar<E>> = derivative(&coeffs).into_iter().map(|e| Scalar::<E>(e)).collect();
+    coeffs.resize(size, Scalar::<E>(E::Fr::zero()));
+
+    let mut domain = EvaluationDomain::from_coeffs(coeffs)?;
+    domain.fft(worker);
+
+    Ok(domain.into_coeffs().into_iter().map(|e| e.0).collect())
+}
+
+// coefficients of prod (x - r_i) over the given roots, built as a subproduct
+// tree so that the large products near the root of the tree go through FFT
+pub fn poly_from_roots<E: Engine>(roots: &[E::Fr], worker: &Worker) -> Vec<E::Fr> {
+    // below this length schoolbook multiplication beats padding to a domain
+    const SCHOOLBOOK_THRESHOLD: usize = 16;
+
+    fn schoolbook<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+        let mut result = vec![F::zero(); a.len() + b.len() - 1];
+        for (i, a) in a.iter().enumerate() {
+            for (j, b) in b.iter().enumerate() {
+                let mut tmp = *a;
+                tmp.mul_assign(b);
+                result[i + j].add_assign(&tmp);
+            }
+        }
+
+        result
+    }
+
+    if roots.is_empty() {
+        return vec![E::Fr::one()];
+    }
+
+    let mut layer: Vec<Vec<E::Fr>> = roots.iter().map(|r| {
+        let mut c = *r;
+        c.negate();
+
+        vec![c, E::Fr::one()]
+    }).collect();
+
+    while layer.len() > 1 {
+        let mut next = Vec::with_capacity(layer.len() / 2 + 1);
+        let mut nodes = layer.into_iter();
+        while let Some(a) = nodes.next() {
+            let product = match nodes.next() {
+                Some(b) if a.len().min(b.len()) <= SCHOOLBOOK_THRESHOLD => schoolbook(&a, &b),
+                Some(b) => multiply_polynomials_with_worker::<E>(a, b, worker),
+                None => a,
+            };
+            next.push(product);
+        }
+
+        layer = next;
+    }
+
+    layer.pop().unwrap()
+}
+
 fn serial_fft<E: Engine>(a: &mut [E::Fr], omega: &E::Fr, log_n: u32) {
     fn bitreverse(mut n: u32, l: u32) -> u32 {
         let mut r = 0;
@@ -1045,6 +1216,38 @@ fn test_mut_distribute_powers() {
     assert!(a == b);
 }
 
+#[test]
+fn test_negate_and_scale_polynomial() {
+    use rand::{self, Rand, Rng};
+    use crate::pairing::bls12_381::Fr;
+
+    const SAMPLES: usize = 100000;
+
+    let rng = &mut rand::thread_rng();
+    let a = (0..SAMPLES).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let x: Fr = rng.gen();
+
+    for cpus in &[1, 2, 3, 8] {
+        let worker = Worker::new_with_cpus(*cpus);
+
+        let mut negated = a.clone();
+        negate_in_place(&mut negated[..], &worker);
+
+        let mut scaled = a.clone();
+        scale_in_place(&mut scaled[..], x, &worker);
+
+        for ((a, n), s) in a.iter().zip(negated.iter()).zip(scaled.iter()) {
+            let mut tmp = *a;
+            tmp.negate();
+            assert_eq!(tmp, *n);
+
+            let mut tmp = *a;
+            tmp.mul_assign(&x);
+            assert_eq!(tmp, *s);
+        }
+    }
+}
+
 
 #[test]
 fn test_trivial_parallel_kate_division() {
@@ -1158,4 +1361,156 @@ fn test_parallel_kate_division() {
     let parallel_q_poly = parallel_kate_divison::<Bls12, _>(&poly, z);
 
     assert_eq!(quotient_poly, parallel_q_poly);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_divide_by_linear() {
+    use rand::{self, Rand, Rng};
+    use crate::pairing::bls12_381::{Bls12, Fr};
+
+    let rng = &mut rand::thread_rng();
+
+    for len in 1..20 {
+        let a = (0..len).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let z: Fr = rng.gen();
+
+        let (q, r) = divide_by_linear(&a, z);
+        assert_eq!(r, evaluate_at_consequitive_powers(&a, Fr::one(), z));
+
+        // q * (x - z) + r == a
+        let mut minus_z = z;
+        minus_z.negate();
+        let mut restored = multiply_polynomials_serial::<Bls12>(q.clone(), vec![minus_z, Fr::one()]);
+        restored[0].add_assign(&r);
+        assert_eq!(restored, a);
+
+        // dividing a polynomial with a root at `z` leaves no remainder
+        let mut vanishing = a.clone();
+        vanishing[0].sub_assign(&r);
+        let (exact_q, exact_r) = divide_by_linear(&vanishing, z);
+        assert!(exact_r.is_zero());
+        assert_eq!(exact_q, q);
+    }
+}
+
+#[test]
+fn test_poly_div() {
+    use rand::{self, Rand};
+    use crate::pairing::bls12_381::{Bls12, Fr};
+
+    let rng = &mut rand::thread_rng();
+
+    let a = (0..13).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let b = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let r = (0..4).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+    let mut num = multiply_polynomials_serial::<Bls12>(a.clone(), b.clone());
+
+    let (q, rem) = poly_div(&num, &b).unwrap();
+    assert_eq!(q, a);
+    assert!(rem.iter().all(|c| c.is_zero()));
+
+    add_polynomials(&mut num[..4], &r);
+    let (q, rem) = poly_div(&num, &b).unwrap();
+    assert_eq!(q, a);
+    assert_eq!(rem, r);
+
+    // a divisor with a zero leading coefficient is trimmed first
+    let mut padded_b = b.clone();
+    padded_b.push(Fr::zero());
+    let (q, rem) = poly_div(&num, &padded_b).unwrap();
+    assert_eq!(q, a);
+    assert_eq!(rem, r);
+
+    assert!(poly_div(&num, &[Fr::zero(), Fr::zero()]).is_err());
+}
+
+#[test]
+fn test_derivative() {
+    use rand::{self, Rand};
+    use crate::pairing::bls12_381::{Bls12, Fr};
+    use crate::domain::{EvaluationDomain, Scalar};
+    use crate::multicore::Worker;
+
+    fn from_u64s(a: &[u64]) -> Vec<Fr> {
+        a.iter().map(|e| Fr::from_str(&format!("{}", e)).unwrap()).collect()
+    }
+
+    fn evaluate_on_domain(a: Vec<Fr>, size: usize) -> Vec<Fr> {
+        let worker = Worker::new();
+        let mut a: Vec<Scalar<Bls12>> = a.into_iter().map(Scalar).collect();
+        a.resize(size, Scalar(Fr::zero()));
+        let mut domain = EvaluationDomain::from_coeffs(a).unwrap();
+        domain.fft(&worker);
+
+        domain.into_coeffs().into_iter().map(|e| e.0).collect()
+    }
+
+    // (3 + 2x + 5x^2 + x^3)' = 2 + 10x + 3x^2
+    let a = from_u64s(&[3, 2, 5, 1]);
+    assert_eq!(derivative(&a), from_u64s(&[2, 10, 3]));
+
+    assert!(derivative(&from_u64s(&[7])).is_empty());
+    assert!(derivative::<Fr>(&[]).is_empty());
+
+    let worker = Worker::new();
+
+    let on_domain = derivative_on_domain::<Bls12>(evaluate_on_domain(a, 8), &worker).unwrap();
+    assert_eq!(on_domain, evaluate_on_domain(from_u64s(&[2, 10, 3]), 8));
+
+    let on_domain = derivative_on_domain::<Bls12>(evaluate_on_domain(from_u64s(&[7]), 8), &worker).unwrap();
+    assert!(on_domain.iter().all(|e| e.is_zero()));
+
+    let rng = &mut rand::thread_rng();
+    let a = (0..64).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let on_domain = derivative_on_domain::<Bls12>(evaluate_on_domain(a.clone(), 64), &worker).unwrap();
+    assert_eq!(on_domain, evaluate_on_domain(derivative(&a), 64));
+
+    assert!(derivative_on_domain::<Bls12>(vec![Fr::one(); 6], &worker).is_err());
+    assert!(derivative_on_domain::<Bls12>(vec![], &worker).is_err());
+}
+
+#[test]
+fn test_poly_from_roots() {
+    use rand::{self, Rand};
+    use crate::pairing::bls12_381::{Bls12, Fr};
+
+    fn evaluate(a: &[Fr], x: &Fr) -> Fr {
+        let mut result = Fr::zero();
+        for coeff in a.iter().rev() {
+            result.mul_assign(x);
+            result.add_assign(coeff);
+        }
+
+        result
+    }
+
+    let worker = Worker::new();
+
+    assert_eq!(poly_from_roots::<Bls12>(&[], &worker), vec![Fr::one()]);
+
+    let rng = &mut rand::thread_rng();
+
+    for num_roots in &[1, 2, 7, 100] {
+        let roots = (0..*num_roots).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let poly = poly_from_roots::<Bls12>(&roots, &worker);
+
+        assert_eq!(poly.len(), num_roots + 1);
+        assert_eq!(poly[*num_roots], Fr::one());
+
+        for r in roots.iter() {
+            assert!(evaluate(&poly, r).is_zero());
+        }
+
+        let x = Fr::rand(rng);
+        let mut expected = Fr::one();
+        for r in roots.iter() {
+            let mut tmp = x;
+            tmp.sub_assign(r);
+            expected.mul_assign(&tmp);
+        }
+
+        assert!(!expected.is_zero());
+        assert_eq!(evaluate(&poly, &x), expected);
+    }
+}
@@ -34,8 +34,16 @@ impl fmt::Display for Fr {
 }
 
 impl Rand for Fr {
+    /// Computes a uniformly random element using rejection sampling
+    /// over the 16 bits the modulus fits into.
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        Fr(Wrapping(rng.gen()) % MODULUS_R)
+        loop {
+            let tmp = Wrapping(rng.gen::<u32>() & 0xffff);
+
+            if tmp < MODULUS_R {
+                return Fr(tmp)
+            }
+        }
     }
 }
 
@@ -502,3 +510,31 @@ impl CurveAffine for Fr {
         Ok(_x)
     }
 }
+
+#[test]
+fn test_fr_rand_uniformity() {
+    use rand::{SeedableRng, XorShiftRng};
+
+    const BUCKETS: usize = 16;
+    const SAMPLES: usize = 1 << 20;
+
+    let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut counts = [0usize; BUCKETS];
+    for _ in 0..SAMPLES {
+        let Fr(Wrapping(v)) = Fr::rand(rng);
+        assert!(v < MODULUS_R.0);
+        counts[(v as usize * BUCKETS) / (MODULUS_R.0 as usize)] += 1;
+    }
+
+    // Buckets are equal up to a single element, so the expected
+    // count is the same for all of them
+    let expected = SAMPLES as f64 / BUCKETS as f64;
+    let chi_square: f64 = counts.iter().map(|&c| {
+        let d = c as f64 - expected;
+        d * d / expected
+    }).sum();
+
+    // critical value for 15 degrees of freedom at p = 0.001
+    assert!(chi_square < 37.7, "chi-square statistic {} is too large", chi_square);
+}
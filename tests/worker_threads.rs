@@ -0,0 +1,36 @@
+// The default number of threads is process-global state, so it is
+// tested in its own binary with a single test to keep the steps ordered.
+#![cfg(feature = "multicore")]
+
+use bellman_ce::worker::Worker;
+
+fn chunk_size(worker: &Worker, elements: usize) -> usize {
+    worker.scope(elements, |_, chunk| chunk)
+}
+
+#[test]
+fn test_default_threads() {
+    std::env::remove_var("BELLMAN_NUM_THREADS");
+
+    // the override takes precedence over the environment
+    Worker::set_default_threads(3);
+    std::env::set_var("BELLMAN_NUM_THREADS", "2");
+
+    let worker = Worker::new();
+    assert_eq!(worker.log_num_cpus(), 1);
+    assert_eq!(chunk_size(&worker, 9), 3);
+
+    // clearing the override falls back to the environment
+    Worker::set_default_threads(0);
+
+    let worker = Worker::new();
+    assert_eq!(worker.log_num_cpus(), 1);
+    assert_eq!(chunk_size(&worker, 8), 4);
+
+    // and then to the number of CPUs
+    std::env::remove_var("BELLMAN_NUM_THREADS");
+
+    let cpus = num_cpus::get();
+    let worker = Worker::new();
+    assert_eq!(chunk_size(&worker, 4 * cpus), 4);
+}